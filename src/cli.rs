@@ -1,5 +1,7 @@
-use GitLit_CLI::GitLitClient;
-use clap::{Parser, Subcommand};
+use GitLit_CLI::forge::{Forge, GitLitForge};
+use GitLit_CLI::{Credentials, GitLitClient};
+use clap::{Parser, Subcommand, ValueEnum};
+use futures::stream::TryStreamExt;
 use serde_json::json;
 
 #[derive(Parser)]
@@ -9,10 +11,54 @@ pub struct Cli {
     #[arg(long, env = "GITLIT_URL")]
     pub url: String,
 
+    #[arg(long, env = "GITLIT_TOKEN")]
+    pub token: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = ForgeKind::Gitlit)]
+    pub forge: ForgeKind,
+
+    #[arg(long, env = "GITLIT_CA_CERT")]
+    pub ca_cert: Option<std::path::PathBuf>,
+
+    #[arg(long, env = "GITLIT_INSECURE")]
+    pub insecure: bool,
+
+    #[arg(long, env = "GITLIT_IGNORE_VERSION")]
+    pub ignore_version: bool,
+
+    #[arg(long, env = "GITLIT_ALLOW_INSECURE_TOKEN_STORE")]
+    pub allow_insecure_token_store: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum ForgeKind {
+    Gitlit,
+    Github,
+    Gitlab,
+    Forgejo,
+}
+
+fn build_forge(kind: ForgeKind, client: GitLitClient) -> anyhow::Result<Box<dyn Forge>> {
+    match kind {
+        ForgeKind::Gitlit => Ok(Box::new(GitLitForge::new(client))),
+        #[cfg(feature = "github")]
+        ForgeKind::Github => Ok(Box::new(GitLit_CLI::forge::GithubForge::new(client))),
+        #[cfg(feature = "gitlab")]
+        ForgeKind::Gitlab => Ok(Box::new(GitLit_CLI::forge::GitlabForge::new(client))),
+        #[cfg(feature = "forgejo")]
+        ForgeKind::Forgejo => Ok(Box::new(GitLit_CLI::forge::ForgejoForge::new(client))),
+        #[cfg(not(feature = "github"))]
+        ForgeKind::Github => anyhow::bail!("github backend not enabled; rebuild with --features github"),
+        #[cfg(not(feature = "gitlab"))]
+        ForgeKind::Gitlab => anyhow::bail!("gitlab backend not enabled; rebuild with --features gitlab"),
+        #[cfg(not(feature = "forgejo"))]
+        ForgeKind::Forgejo => anyhow::bail!("forgejo backend not enabled; rebuild with --features forgejo"),
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     Login {
@@ -29,6 +75,9 @@ pub enum Commands {
         #[arg(long)] owner: Option<String>,
         #[arg(long)] filter: Option<String>,
         #[arg(long)] q: Option<String>,
+        #[arg(long)] page: Option<u32>,
+        #[arg(long)] per_page: Option<u32>,
+        #[arg(long)] all: bool,
     },
     CreateRepo {
         #[arg(long)] name: String,
@@ -37,9 +86,11 @@ pub enum Commands {
     },
     DeleteRepo { #[arg(long)] id: String },
     Branches { #[arg(long)] id: String },
-    Commits { #[arg(long)] id: String, #[arg(long)] branch: Option<String>, #[arg(long)] limit: Option<u32> },
+    Commits { #[arg(long)] id: String, #[arg(long)] branch: Option<String>, #[arg(long)] limit: Option<u32>, #[arg(long)] page: Option<u32>, #[arg(long)] per_page: Option<u32>, #[arg(long)] all: bool },
     Content { #[arg(long)] id: String, #[arg(long)] path: Option<String>, #[arg(long)] branch: Option<String>, #[arg(long)] commit: Option<String> },
     Download { #[arg(long)] id: String, #[arg(long)] path: Option<String>, #[arg(long)] branch: Option<String>, #[arg(long)] commit: Option<String>, #[arg(long)] out: String },
+    Upload { #[arg(long)] id: String, #[arg(long)] branch: String, #[arg(long)] path: String, #[arg(long)] file: String, #[arg(long)] message: String, #[arg(long)] parent: Option<String> },
+    CreateBranch { #[arg(long)] id: String, #[arg(long)] name: String, #[arg(long)] from: Option<String> },
 }
 
 #[tokio::main]
@@ -52,7 +103,24 @@ async fn main() -> anyhow::Result<()> {
         format!("https://{}", cli.url)
     };
 
-    let client = GitLitClient::new(&url)?;
+    let mut builder = GitLitClient::builder(&url);
+    if let Some(token) = &cli.token {
+        builder = builder.credentials(Credentials::Token(token.clone()));
+    }
+    if let Some(ca_cert) = &cli.ca_cert {
+        builder = builder.ca_cert(ca_cert);
+    }
+    if cli.insecure {
+        builder = builder.accept_invalid_certs(true);
+    }
+    if cli.ignore_version {
+        builder = builder.ignore_version(true);
+    }
+    if cli.allow_insecure_token_store {
+        builder = builder.allow_insecure_token_store(true);
+    }
+    let client = builder.build()?;
+    let forge = build_forge(cli.forge, client.clone())?;
     match cli.command {
         Commands::Login { login, password } => {
             let token = client.login(&login, &password).await?;
@@ -76,37 +144,60 @@ async fn main() -> anyhow::Result<()> {
             let res = client.register(&username, &email, &password).await?;
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","response": res}))?);
         }
-        Commands::Repos { owner, filter, q } => {
-            let repos = client.list_repos(owner.as_deref(), filter.as_deref(), q.as_deref()).await?;
+        Commands::Repos { owner, filter, q, page, per_page, all } => {
+            let first = forge.list_repos(owner.as_deref(), filter.as_deref(), q.as_deref(), page, per_page).await?;
+            let repos = if all {
+                first.stream().try_collect::<Vec<_>>().await?
+            } else {
+                first.into_items()
+            };
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","repos": repos}))?);
         }
         Commands::CreateRepo { name, description, private } => {
-            let repo = client.create_repo(&name, description.as_deref(), private).await?;
+            let repo = forge.create_repo(&name, description.as_deref(), private).await?;
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","repo": repo}))?);
         }
         Commands::DeleteRepo { id } => {
-            match client.delete_repo(&id).await {
+            match forge.delete_repo(&id).await {
                 Ok(ok) => println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","ok": ok.ok}))?),
                 Err(e) => println!("{}", serde_json::to_string_pretty(&json!({"status":"error","error": e.to_string()}))?),
             }
         }
         Commands::Branches { id } => {
-            let br = client.branches(&id).await?;
+            let br = forge.branches(&id).await?;
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","branches": br.branches}))?);
         }
-        Commands::Commits { id, branch, limit } => {
-            let commits = client.commits(&id, branch.as_deref(), limit).await?;
+        Commands::Commits { id, branch, limit, page, per_page, all } => {
+            let first = forge.commits(&id, branch.as_deref(), limit, page, per_page).await?;
+            let commits = if all {
+                first.stream().try_collect::<Vec<_>>().await?
+            } else {
+                first.into_items()
+            };
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","commits": commits}))?);
         }
         Commands::Content { id, path, branch, commit } => {
-            let content = client.content(&id, path.as_deref(), branch.as_deref(), commit.as_deref()).await?;
+            let content = forge.content(&id, path.as_deref(), branch.as_deref(), commit.as_deref()).await?;
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","content": content}))?);
         }
         Commands::Download { id, path, branch, commit, out } => {
-            let bytes = client.download(&id, path.as_deref(), branch.as_deref(), commit.as_deref()).await?;
+            let bytes = forge.download(&id, path.as_deref(), branch.as_deref(), commit.as_deref()).await?;
             std::fs::write(&out, &bytes)?;
             println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","bytes": bytes.len()}))?);
         }
+        Commands::Upload { id, branch, path, file, message, parent } => {
+            let bytes = std::fs::read(&file)?;
+            match client.put_content(&id, &branch, &path, &bytes, &message, parent.as_deref()).await {
+                Ok(commit) => println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","commit": commit}))?),
+                Err(e) => println!("{}", serde_json::to_string_pretty(&json!({"status":"error","error": e.to_string()}))?),
+            }
+        }
+        Commands::CreateBranch { id, name, from } => {
+            match client.create_branch(&id, &name, from.as_deref()).await {
+                Ok(branch) => println!("{}", serde_json::to_string_pretty(&json!({"status":"ok","branch": branch}))?),
+                Err(e) => println!("{}", serde_json::to_string_pretty(&json!({"status":"error","error": e.to_string()}))?),
+            }
+        }
     }
     Ok(())
 }
@@ -0,0 +1,714 @@
+//! Pluggable forge backends.
+//!
+//! The [`Forge`] trait abstracts the network surface the CLI needs so the same
+//! binary can talk to a GitLit server or, behind the `github`/`gitlab`/`forgejo`
+//! feature flags, to those forges' REST APIs. [`GitLitForge`] is the default and
+//! wraps today's [`GitLitClient`] routes; the other impls translate each call
+//! into the corresponding route and auth header for that forge.
+
+use async_trait::async_trait;
+
+use crate::{
+    AuthScheme, Branch, BranchesResponse, BrancheDeleteResponse, CommitInfo, ContentResponse,
+    Credentials, GitLitClient, GitLitError, OkResponse, Page, Repository, TreeEntry,
+};
+
+/// The repository operations a forge backend must provide.
+#[async_trait]
+pub trait Forge: Send + Sync {
+    async fn list_repos(&self, owner: Option<&str>, filter: Option<&str>, q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError>;
+    async fn create_repo(&self, name: &str, description: Option<&str>, is_private: Option<bool>) -> Result<Repository, GitLitError>;
+    async fn delete_repo(&self, id: &str) -> Result<OkResponse, GitLitError>;
+    async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError>;
+    async fn delete_branch(&self, id: &str, branch: &str) -> Result<BrancheDeleteResponse, GitLitError>;
+    async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError>;
+    async fn content(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<ContentResponse, GitLitError>;
+    async fn download(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<Vec<u8>, GitLitError>;
+}
+
+/// The default backend: GitLit's own `/api/v1/...` routes.
+pub struct GitLitForge {
+    client: GitLitClient,
+}
+
+impl GitLitForge {
+    pub fn new(client: GitLitClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Forge for GitLitForge {
+    async fn list_repos(&self, owner: Option<&str>, filter: Option<&str>, q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError> {
+        self.client.list_repos(owner, filter, q, page, per_page).await
+    }
+    async fn create_repo(&self, name: &str, description: Option<&str>, is_private: Option<bool>) -> Result<Repository, GitLitError> {
+        self.client.create_repo(name, description, is_private).await
+    }
+    async fn delete_repo(&self, id: &str) -> Result<OkResponse, GitLitError> {
+        self.client.delete_repo(id).await
+    }
+    async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError> {
+        self.client.branches(id).await
+    }
+    async fn delete_branch(&self, id: &str, branch: &str) -> Result<BrancheDeleteResponse, GitLitError> {
+        self.client.delete_branch(id, branch).await
+    }
+    async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError> {
+        self.client.commits(id, branch, limit, page, per_page).await
+    }
+    async fn content(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<ContentResponse, GitLitError> {
+        self.client.content(id, path, branch, commit).await
+    }
+    async fn download(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<Vec<u8>, GitLitError> {
+        self.client.download(id, path, branch, commit).await
+    }
+}
+
+/// Attach a `Credentials::Token` as an `Authorization: Bearer` header, as
+/// GitHub/Forgejo expect (GitLab uses [`private_token`] instead). Other
+/// credentials leave the request untouched.
+#[cfg(any(feature = "github", feature = "gitlab", feature = "forgejo"))]
+fn bearer(req: reqwest::RequestBuilder, creds: &Credentials) -> reqwest::RequestBuilder {
+    match creds {
+        Credentials::Token(token) => req.bearer_auth(token),
+        _ => req,
+    }
+}
+
+#[cfg(feature = "gitlab")]
+fn private_token(req: reqwest::RequestBuilder, creds: &Credentials) -> reqwest::RequestBuilder {
+    match creds {
+        Credentials::Token(token) => req.header("PRIVATE-TOKEN", token),
+        _ => req,
+    }
+}
+
+/// Percent-encode a file path for GitLab's `repository/files/{path}` routes,
+/// which expect the whole path (slashes included) URL-encoded. Only the
+/// unreserved set is left as-is.
+#[cfg(feature = "gitlab")]
+fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for &b in path.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+/// Best-effort conversion of an RFC 3339 timestamp (`2023-01-02T03:04:05Z`) to
+/// Unix epoch seconds, returning 0 when the string can't be parsed. Every forge
+/// reports commit times as ISO strings whereas [`CommitInfo`] stores seconds.
+#[cfg(any(feature = "github", feature = "gitlab", feature = "forgejo"))]
+fn rfc3339_to_epoch(s: &str) -> i64 {
+    fn parse(s: &str) -> Option<i64> {
+        let y: i64 = s.get(0..4)?.parse().ok()?;
+        let mo: i64 = s.get(5..7)?.parse().ok()?;
+        let d: i64 = s.get(8..10)?.parse().ok()?;
+        let h: i64 = s.get(11..13)?.parse().ok()?;
+        let mi: i64 = s.get(14..16)?.parse().ok()?;
+        let se: i64 = s.get(17..19)?.parse().ok()?;
+        // Days since the Unix epoch via Howard Hinnant's days_from_civil.
+        let y = if mo <= 2 { y - 1 } else { y };
+        let era = (if y >= 0 { y } else { y - 399 }) / 400;
+        let yoe = y - era * 400;
+        let doy = (153 * (if mo > 2 { mo - 3 } else { mo + 9 }) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+        Some(days * 86400 + h * 3600 + mi * 60 + se)
+    }
+    parse(s).unwrap_or(0)
+}
+
+/// Keep only the first line of a commit message, matching [`CommitInfo::subject`].
+#[cfg(any(feature = "github", feature = "gitlab", feature = "forgejo"))]
+fn subject_of(message: &str) -> String {
+    message.lines().next().unwrap_or("").to_string()
+}
+
+/// GitHub REST v3 response shapes and their mapping into the common structs.
+#[cfg(feature = "github")]
+mod github_dto {
+    use super::{rfc3339_to_epoch, subject_of, Branch, CommitInfo, ContentResponse, GitLitError, Repository, TreeEntry};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Owner { login: String }
+
+    #[derive(Deserialize)]
+    struct Repo {
+        id: u64,
+        name: String,
+        #[serde(default)] description: Option<String>,
+        owner: Owner,
+        #[serde(rename = "private")] is_private: bool,
+        #[serde(default)] created_at: Option<String>,
+        #[serde(default)] updated_at: Option<String>,
+        #[serde(default)] parent: Option<Box<Repo>>,
+    }
+
+    impl From<Repo> for Repository {
+        fn from(r: Repo) -> Self {
+            Repository {
+                _id: r.id.to_string(),
+                user: r.owner.login,
+                name: r.name,
+                description: r.description.unwrap_or_default(),
+                is_private: r.is_private,
+                created_at: r.created_at.unwrap_or_default(),
+                updated_at: r.updated_at.unwrap_or_default(),
+                forked_from: r.parent.map(|p| format!("{}/{}", p.owner.login, p.name)),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct CommitRef { sha: String }
+
+    #[derive(Deserialize)]
+    struct GitBranch { name: String, commit: CommitRef }
+
+    impl From<GitBranch> for Branch {
+        fn from(b: GitBranch) -> Self {
+            Branch { is_head: false, name: b.name, oid: b.commit.sha, upstream: None }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Author { #[serde(default)] name: String, #[serde(default)] email: String, #[serde(default)] date: String }
+
+    #[derive(Deserialize)]
+    struct CommitBody { message: String, author: Author }
+
+    #[derive(Deserialize)]
+    struct Commit { sha: String, commit: CommitBody }
+
+    impl From<Commit> for CommitInfo {
+        fn from(c: Commit) -> Self {
+            CommitInfo {
+                hash: c.sha,
+                name: c.commit.author.name,
+                email: c.commit.author.email,
+                timestamp_secs: rfc3339_to_epoch(&c.commit.author.date),
+                subject: subject_of(&c.commit.message),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct DirEntry { path: String, #[serde(rename = "type")] kind: String, sha: String, #[serde(default)] size: Option<i64> }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Content {
+        File { content: String },
+        Dir(Vec<DirEntry>),
+    }
+
+    pub(super) fn decode_repos(bytes: &[u8]) -> Result<Vec<Repository>, GitLitError> {
+        let raw: Vec<Repo> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn decode_commits(bytes: &[u8]) -> Result<Vec<CommitInfo>, GitLitError> {
+        let raw: Vec<Commit> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn map_repo(bytes: &[u8]) -> Result<Repository, GitLitError> {
+        Ok(serde_json::from_slice::<Repo>(bytes)?.into())
+    }
+
+    pub(super) fn map_branches(bytes: &[u8]) -> Result<Vec<Branch>, GitLitError> {
+        let raw: Vec<GitBranch> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn map_content(bytes: &[u8]) -> Result<ContentResponse, GitLitError> {
+        Ok(match serde_json::from_slice::<Content>(bytes)? {
+            Content::File { content } => ContentResponse::blob { content_base64: content.replace(['\n', '\r'], "") },
+            Content::Dir(entries) => ContentResponse::tree {
+                entries: entries
+                    .into_iter()
+                    .map(|e| TreeEntry { mode: String::new(), kind: serde_json::Value::String(e.kind), oid: e.sha, path: e.path, size: e.size })
+                    .collect(),
+            },
+        })
+    }
+}
+
+/// GitLab REST v4 response shapes and their mapping into the common structs.
+#[cfg(feature = "gitlab")]
+mod gitlab_dto {
+    use super::{rfc3339_to_epoch, subject_of, Branch, CommitInfo, ContentResponse, GitLitError, Repository};
+    use base64::Engine;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Project {
+        id: u64,
+        #[serde(default)] name: String,
+        #[serde(default)] namespace: Option<Namespace>,
+        #[serde(default)] description: Option<String>,
+        #[serde(default)] visibility: Option<String>,
+        #[serde(default)] created_at: Option<String>,
+        #[serde(default)] last_activity_at: Option<String>,
+        #[serde(default)] forked_from_project: Option<ForkedFrom>,
+    }
+
+    #[derive(Deserialize)]
+    struct Namespace { #[serde(default)] full_path: String }
+
+    #[derive(Deserialize)]
+    struct ForkedFrom { #[serde(default)] path_with_namespace: String }
+
+    impl From<Project> for Repository {
+        fn from(p: Project) -> Self {
+            Repository {
+                _id: p.id.to_string(),
+                user: p.namespace.map(|n| n.full_path).unwrap_or_default(),
+                name: p.name,
+                description: p.description.unwrap_or_default(),
+                is_private: p.visibility.as_deref() != Some("public"),
+                created_at: p.created_at.unwrap_or_default(),
+                updated_at: p.last_activity_at.unwrap_or_default(),
+                forked_from: p.forked_from_project.map(|f| f.path_with_namespace),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct GitBranch { name: String, #[serde(default)] default: bool, commit: Commit }
+
+    #[derive(Deserialize)]
+    struct Commit {
+        id: String,
+        #[serde(default)] author_name: String,
+        #[serde(default)] author_email: String,
+        #[serde(default)] created_at: String,
+        #[serde(default)] title: String,
+        #[serde(default)] message: String,
+    }
+
+    impl From<GitBranch> for Branch {
+        fn from(b: GitBranch) -> Self {
+            Branch { is_head: b.default, name: b.name, oid: b.commit.id, upstream: None }
+        }
+    }
+
+    impl From<Commit> for CommitInfo {
+        fn from(c: Commit) -> Self {
+            let subject = if c.title.is_empty() { subject_of(&c.message) } else { c.title };
+            CommitInfo {
+                hash: c.id,
+                name: c.author_name,
+                email: c.author_email,
+                timestamp_secs: rfc3339_to_epoch(&c.created_at),
+                subject,
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct File { content: String, #[serde(default)] encoding: String }
+
+    pub(super) fn decode_repos(bytes: &[u8]) -> Result<Vec<Repository>, GitLitError> {
+        let raw: Vec<Project> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn decode_commits(bytes: &[u8]) -> Result<Vec<CommitInfo>, GitLitError> {
+        let raw: Vec<Commit> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn map_repo(bytes: &[u8]) -> Result<Repository, GitLitError> {
+        Ok(serde_json::from_slice::<Project>(bytes)?.into())
+    }
+
+    pub(super) fn map_branches(bytes: &[u8]) -> Result<Vec<Branch>, GitLitError> {
+        let raw: Vec<GitBranch> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    /// GitLab's file endpoint returns base64 content regardless of type; rewrap
+    /// it as a blob (GitLab has no combined tree/blob response).
+    pub(super) fn map_content(bytes: &[u8]) -> Result<ContentResponse, GitLitError> {
+        let file: File = serde_json::from_slice(bytes)?;
+        let content_base64 = if file.encoding == "base64" {
+            file.content.replace(['\n', '\r'], "")
+        } else {
+            base64::engine::general_purpose::STANDARD.encode(file.content.as_bytes())
+        };
+        Ok(ContentResponse::blob { content_base64 })
+    }
+}
+
+/// Forgejo/Gitea response shapes and their mapping into the common structs.
+#[cfg(feature = "forgejo")]
+mod forgejo_dto {
+    use super::{rfc3339_to_epoch, subject_of, Branch, CommitInfo, ContentResponse, GitLitError, Repository, TreeEntry};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Owner { #[serde(default)] login: String }
+
+    #[derive(Deserialize)]
+    struct Repo {
+        id: u64,
+        name: String,
+        #[serde(default)] description: String,
+        #[serde(default)] owner: Option<Owner>,
+        #[serde(rename = "private", default)] is_private: bool,
+        #[serde(default)] created_at: Option<String>,
+        #[serde(default)] updated_at: Option<String>,
+        #[serde(default)] fork: bool,
+        #[serde(default)] parent: Option<Box<Repo>>,
+    }
+
+    impl From<Repo> for Repository {
+        fn from(r: Repo) -> Self {
+            let forked_from = if r.fork {
+                r.parent.map(|p| format!("{}/{}", p.owner.map(|o| o.login).unwrap_or_default(), p.name))
+            } else {
+                None
+            };
+            Repository {
+                _id: r.id.to_string(),
+                user: r.owner.map(|o| o.login).unwrap_or_default(),
+                name: r.name,
+                description: r.description,
+                is_private: r.is_private,
+                created_at: r.created_at.unwrap_or_default(),
+                updated_at: r.updated_at.unwrap_or_default(),
+                forked_from,
+            }
+        }
+    }
+
+    /// Forgejo wraps repo searches in `{ "data": [ ... ] }`; plain listings are
+    /// a bare array. Accept either.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RepoList {
+        Search { data: Vec<Repo> },
+        Plain(Vec<Repo>),
+    }
+
+    #[derive(Deserialize)]
+    struct CommitRef { sha: String }
+
+    #[derive(Deserialize)]
+    struct GitBranch { name: String, commit: CommitRef }
+
+    impl From<GitBranch> for Branch {
+        fn from(b: GitBranch) -> Self {
+            Branch { is_head: false, name: b.name, oid: b.commit.sha, upstream: None }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Author { #[serde(default)] name: String, #[serde(default)] email: String, #[serde(default)] date: String }
+
+    #[derive(Deserialize)]
+    struct CommitBody { message: String, author: Author }
+
+    #[derive(Deserialize)]
+    struct Commit { sha: String, commit: CommitBody }
+
+    impl From<Commit> for CommitInfo {
+        fn from(c: Commit) -> Self {
+            CommitInfo {
+                hash: c.sha,
+                name: c.commit.author.name,
+                email: c.commit.author.email,
+                timestamp_secs: rfc3339_to_epoch(&c.commit.author.date),
+                subject: subject_of(&c.commit.message),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct DirEntry { path: String, #[serde(rename = "type")] kind: String, sha: String, #[serde(default)] size: Option<i64> }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Content {
+        File { content: String },
+        Dir(Vec<DirEntry>),
+    }
+
+    pub(super) fn decode_repos(bytes: &[u8]) -> Result<Vec<Repository>, GitLitError> {
+        let raw = match serde_json::from_slice::<RepoList>(bytes)? {
+            RepoList::Search { data } => data,
+            RepoList::Plain(list) => list,
+        };
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn decode_commits(bytes: &[u8]) -> Result<Vec<CommitInfo>, GitLitError> {
+        let raw: Vec<Commit> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn map_repo(bytes: &[u8]) -> Result<Repository, GitLitError> {
+        Ok(serde_json::from_slice::<Repo>(bytes)?.into())
+    }
+
+    pub(super) fn map_branches(bytes: &[u8]) -> Result<Vec<Branch>, GitLitError> {
+        let raw: Vec<GitBranch> = serde_json::from_slice(bytes)?;
+        Ok(raw.into_iter().map(Into::into).collect())
+    }
+
+    pub(super) fn map_content(bytes: &[u8]) -> Result<ContentResponse, GitLitError> {
+        Ok(match serde_json::from_slice::<Content>(bytes)? {
+            Content::File { content } => ContentResponse::blob { content_base64: content.replace(['\n', '\r'], "") },
+            Content::Dir(entries) => ContentResponse::tree {
+                entries: entries
+                    .into_iter()
+                    .map(|e| TreeEntry { mode: String::new(), kind: serde_json::Value::String(e.kind), oid: e.sha, path: e.path, size: e.size })
+                    .collect(),
+            },
+        })
+    }
+}
+
+/// GitHub REST v3 backend. Translates the trait calls into `/repos/...` routes
+/// and `Authorization: Bearer` auth.
+#[cfg(feature = "github")]
+pub struct GithubForge {
+    client: GitLitClient,
+}
+
+#[cfg(feature = "github")]
+impl GithubForge {
+    pub fn new(client: GitLitClient) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "github")]
+#[async_trait]
+impl Forge for GithubForge {
+    async fn list_repos(&self, owner: Option<&str>, _filter: Option<&str>, _q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError> {
+        let url = match owner {
+            Some(owner) => format!("{}/users/{}/repos", self.client.base(), owner),
+            None => format!("{}/user/repos", self.client.base()),
+        };
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        if let Some(per_page) = per_page { req = req.query(&[("per_page", per_page)]); }
+        self.client.fetch_page_with(req, AuthScheme::Bearer, github_dto::decode_repos).await
+    }
+    async fn create_repo(&self, name: &str, description: Option<&str>, is_private: Option<bool>) -> Result<Repository, GitLitError> {
+        let url = format!("{}/user/repos", self.client.base());
+        let req = bearer(self.client.http().post(url), self.client.credentials())
+            .json(&serde_json::json!({ "name": name, "description": description, "private": is_private }));
+        let res = req.send().await?;
+        if res.status() != reqwest::StatusCode::CREATED { return Err(GitLitError::Auth(format!("create_repo failed: {}", res.status()))); }
+        github_dto::map_repo(&res.bytes().await?)
+    }
+    async fn delete_repo(&self, id: &str) -> Result<OkResponse, GitLitError> {
+        let url = format!("{}/repos/{}", self.client.base(), id);
+        let res = bearer(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_repo failed: {}", res.status()))); }
+        Ok(OkResponse { ok: true })
+    }
+    async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError> {
+        let url = format!("{}/repos/{}/branches", self.client.base(), id);
+        let res = bearer(self.client.http().get(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("branches failed: {}", res.status()))); }
+        Ok(BranchesResponse { branches: github_dto::map_branches(&res.bytes().await?)? })
+    }
+    async fn delete_branch(&self, id: &str, branch: &str) -> Result<BrancheDeleteResponse, GitLitError> {
+        let url = format!("{}/repos/{}/git/refs/heads/{}", self.client.base(), id, branch);
+        let res = bearer(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_branch failed: {}", res.status()))); }
+        Ok(BrancheDeleteResponse { message: format!("deleted {}", branch) })
+    }
+    async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError> {
+        let url = format!("{}/repos/{}/commits", self.client.base(), id);
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(b) = branch { req = req.query(&[("sha", b)]); }
+        if let Some(l) = limit.or(per_page) { req = req.query(&[("per_page", l)]); }
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        self.client.fetch_page_with(req, AuthScheme::Bearer, github_dto::decode_commits).await
+    }
+    async fn content(&self, id: &str, path: Option<&str>, branch: Option<&str>, _commit: Option<&str>) -> Result<ContentResponse, GitLitError> {
+        let url = format!("{}/repos/{}/contents/{}", self.client.base(), id, path.unwrap_or(""));
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(b) = branch { req = req.query(&[("ref", b)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("content failed: {}", res.status()))); }
+        github_dto::map_content(&res.bytes().await?)
+    }
+    async fn download(&self, id: &str, path: Option<&str>, branch: Option<&str>, _commit: Option<&str>) -> Result<Vec<u8>, GitLitError> {
+        let url = format!("{}/repos/{}/contents/{}", self.client.base(), id, path.unwrap_or(""));
+        let mut req = bearer(self.client.http().get(url), self.client.credentials()).header("Accept", "application/vnd.github.raw");
+        if let Some(b) = branch { req = req.query(&[("ref", b)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("download failed: {}", res.status()))); }
+        Ok(res.bytes().await?.to_vec())
+    }
+}
+
+/// GitLab REST v4 backend. Uses `/projects/...` routes and `PRIVATE-TOKEN` auth.
+#[cfg(feature = "gitlab")]
+pub struct GitlabForge {
+    client: GitLitClient,
+}
+
+#[cfg(feature = "gitlab")]
+impl GitlabForge {
+    pub fn new(client: GitLitClient) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "gitlab")]
+#[async_trait]
+impl Forge for GitlabForge {
+    async fn list_repos(&self, owner: Option<&str>, _filter: Option<&str>, q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError> {
+        let url = format!("{}/api/v4/projects", self.client.base());
+        let mut req = private_token(self.client.http().get(url), self.client.credentials());
+        if let Some(owner) = owner { req = req.query(&[("owned", "true"), ("search_namespaces", owner)]); }
+        if let Some(q) = q { req = req.query(&[("search", q)]); }
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        if let Some(per_page) = per_page { req = req.query(&[("per_page", per_page)]); }
+        self.client.fetch_page_with(req, AuthScheme::PrivateToken, gitlab_dto::decode_repos).await
+    }
+    async fn create_repo(&self, name: &str, description: Option<&str>, is_private: Option<bool>) -> Result<Repository, GitLitError> {
+        let url = format!("{}/api/v4/projects", self.client.base());
+        let visibility = if is_private.unwrap_or(false) { "private" } else { "public" };
+        let req = private_token(self.client.http().post(url), self.client.credentials())
+            .json(&serde_json::json!({ "name": name, "description": description, "visibility": visibility }));
+        let res = req.send().await?;
+        if res.status() != reqwest::StatusCode::CREATED { return Err(GitLitError::Auth(format!("create_repo failed: {}", res.status()))); }
+        gitlab_dto::map_repo(&res.bytes().await?)
+    }
+    async fn delete_repo(&self, id: &str) -> Result<OkResponse, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}", self.client.base(), id);
+        let res = private_token(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_repo failed: {}", res.status()))); }
+        Ok(OkResponse { ok: true })
+    }
+    async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}/repository/branches", self.client.base(), id);
+        let res = private_token(self.client.http().get(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("branches failed: {}", res.status()))); }
+        Ok(BranchesResponse { branches: gitlab_dto::map_branches(&res.bytes().await?)? })
+    }
+    async fn delete_branch(&self, id: &str, branch: &str) -> Result<BrancheDeleteResponse, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}/repository/branches/{}", self.client.base(), id, branch);
+        let res = private_token(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_branch failed: {}", res.status()))); }
+        Ok(BrancheDeleteResponse { message: format!("deleted {}", branch) })
+    }
+    async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}/repository/commits", self.client.base(), id);
+        let mut req = private_token(self.client.http().get(url), self.client.credentials());
+        if let Some(b) = branch { req = req.query(&[("ref_name", b)]); }
+        if let Some(l) = limit.or(per_page) { req = req.query(&[("per_page", l)]); }
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        self.client.fetch_page_with(req, AuthScheme::PrivateToken, gitlab_dto::decode_commits).await
+    }
+    async fn content(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<ContentResponse, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}/repository/files/{}", self.client.base(), id, encode_path(path.unwrap_or("")));
+        let mut req = private_token(self.client.http().get(url), self.client.credentials());
+        if let Some(r) = commit.or(branch) { req = req.query(&[("ref", r)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("content failed: {}", res.status()))); }
+        gitlab_dto::map_content(&res.bytes().await?)
+    }
+    async fn download(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<Vec<u8>, GitLitError> {
+        let url = format!("{}/api/v4/projects/{}/repository/files/{}/raw", self.client.base(), id, encode_path(path.unwrap_or("")));
+        let mut req = private_token(self.client.http().get(url), self.client.credentials());
+        if let Some(r) = commit.or(branch) { req = req.query(&[("ref", r)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("download failed: {}", res.status()))); }
+        Ok(res.bytes().await?.to_vec())
+    }
+}
+
+/// Forgejo/Gitea backend. Shares GitHub-compatible-ish routes under `/api/v1`
+/// with `Authorization: Bearer` auth.
+#[cfg(feature = "forgejo")]
+pub struct ForgejoForge {
+    client: GitLitClient,
+}
+
+#[cfg(feature = "forgejo")]
+impl ForgejoForge {
+    pub fn new(client: GitLitClient) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "forgejo")]
+#[async_trait]
+impl Forge for ForgejoForge {
+    async fn list_repos(&self, owner: Option<&str>, _filter: Option<&str>, q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError> {
+        let url = match owner {
+            Some(owner) => format!("{}/api/v1/users/{}/repos", self.client.base(), owner),
+            None => format!("{}/api/v1/repos/search", self.client.base()),
+        };
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(q) = q { req = req.query(&[("q", q)]); }
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        if let Some(per_page) = per_page { req = req.query(&[("limit", per_page)]); }
+        self.client.fetch_page_with(req, AuthScheme::Bearer, forgejo_dto::decode_repos).await
+    }
+    async fn create_repo(&self, name: &str, description: Option<&str>, is_private: Option<bool>) -> Result<Repository, GitLitError> {
+        let url = format!("{}/api/v1/user/repos", self.client.base());
+        let req = bearer(self.client.http().post(url), self.client.credentials())
+            .json(&serde_json::json!({ "name": name, "description": description, "private": is_private }));
+        let res = req.send().await?;
+        if res.status() != reqwest::StatusCode::CREATED { return Err(GitLitError::Auth(format!("create_repo failed: {}", res.status()))); }
+        forgejo_dto::map_repo(&res.bytes().await?)
+    }
+    async fn delete_repo(&self, id: &str) -> Result<OkResponse, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}", self.client.base(), id);
+        let res = bearer(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_repo failed: {}", res.status()))); }
+        Ok(OkResponse { ok: true })
+    }
+    async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}/branches", self.client.base(), id);
+        let res = bearer(self.client.http().get(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("branches failed: {}", res.status()))); }
+        Ok(BranchesResponse { branches: forgejo_dto::map_branches(&res.bytes().await?)? })
+    }
+    async fn delete_branch(&self, id: &str, branch: &str) -> Result<BrancheDeleteResponse, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}/branches/{}", self.client.base(), id, branch);
+        let res = bearer(self.client.http().delete(url), self.client.credentials()).send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_branch failed: {}", res.status()))); }
+        Ok(BrancheDeleteResponse { message: format!("deleted {}", branch) })
+    }
+    async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}/commits", self.client.base(), id);
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(b) = branch { req = req.query(&[("sha", b)]); }
+        if let Some(l) = limit.or(per_page) { req = req.query(&[("limit", l)]); }
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        self.client.fetch_page_with(req, AuthScheme::Bearer, forgejo_dto::decode_commits).await
+    }
+    async fn content(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<ContentResponse, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}/contents/{}", self.client.base(), id, path.unwrap_or(""));
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(r) = commit.or(branch) { req = req.query(&[("ref", r)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("content failed: {}", res.status()))); }
+        forgejo_dto::map_content(&res.bytes().await?)
+    }
+    async fn download(&self, id: &str, path: Option<&str>, branch: Option<&str>, commit: Option<&str>) -> Result<Vec<u8>, GitLitError> {
+        let url = format!("{}/api/v1/repos/{}/raw/{}", self.client.base(), id, path.unwrap_or(""));
+        let mut req = bearer(self.client.http().get(url), self.client.credentials());
+        if let Some(r) = commit.or(branch) { req = req.query(&[("ref", r)]); }
+        let res = req.send().await?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("download failed: {}", res.status()))); }
+        Ok(res.bytes().await?.to_vec())
+    }
+}
@@ -1,7 +1,12 @@
+use base64::Engine;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+pub mod forge;
+
 #[derive(Debug, Error)]
 pub enum GitLitError {
     #[error("http error: {0}")]
@@ -14,8 +19,21 @@ pub enum GitLitError {
     Unauthorized,
     #[error("auth error: {0}")]
     Auth(String),
+    #[error("api version mismatch: client {client}, server {server}")]
+    VersionMismatch { client: String, server: String },
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error("token file {0} is group/world-accessible or owned by another user; pass --allow-insecure-token-store to use it")]
+    InsecureTokenStore(PathBuf),
 }
 
+/// API version this CLI speaks, sent as the `X-GITLIT-VERSION` header and
+/// checked against the server's response header.
+pub const API_VERSION: &str = "1";
+
+/// Name of the header carrying the negotiated API version.
+pub const VERSION_HEADER: &str = "X-GITLIT-VERSION";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
     pub _id: String,
@@ -62,23 +80,255 @@ pub enum ContentResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TreeEntry { pub mode: String, pub kind: serde_json::Value, pub oid: String, pub path: String, pub size: Option<i64> }
 
+/// A single file change staged into a commit: its repository path and the
+/// base64-encoded contents to write there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange { pub path: String, pub content_base64: String }
+
+/// One page of a paginated list endpoint, plus the links needed to walk to the
+/// neighbouring pages. The RFC 5988 `Link` response header is parsed into the
+/// `next`/`prev`/`last` URLs so callers can page without reconstructing query
+/// strings by hand.
+#[derive(Clone)]
+pub struct Page<T> {
+    items: Vec<T>,
+    next: Option<String>,
+    prev: Option<String>,
+    last: Option<String>,
+    scheme: AuthScheme,
+    decode: PageDecoder<T>,
+    client: GitLitClient,
+}
+
+impl<T: Send + 'static> Page<T> {
+    /// The items deserialized from this page.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consume the page and return its items.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Fetch the next page, or `None` when there is no `rel="next"` link.
+    pub async fn next_page(&self) -> Result<Option<Page<T>>, GitLitError> {
+        match &self.next {
+            Some(url) => {
+                let req = self.client.auth_with(self.client.http.get(url), self.scheme).await?;
+                Ok(Some(self.client.fetch_page_with(req, self.scheme, self.decode).await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the previous page, or `None` when there is no `rel="prev"` link.
+    pub async fn prev_page(&self) -> Result<Option<Page<T>>, GitLitError> {
+        match &self.prev {
+            Some(url) => {
+                let req = self.client.auth_with(self.client.http.get(url), self.scheme).await?;
+                Ok(Some(self.client.fetch_page_with(req, self.scheme, self.decode).await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// URL of the last page, if the server advertised a `rel="last"` link.
+    pub fn last_url(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
+    /// Adapt this page into a [`Stream`] that yields items across page
+    /// boundaries, following `next` links until none remains.
+    pub fn stream(self) -> impl Stream<Item = Result<T, GitLitError>> {
+        stream::try_unfold(Some(self), |state| async move {
+            match state {
+                None => Ok::<_, GitLitError>(None),
+                Some(page) => {
+                    let next = page.next_page().await?;
+                    Ok(Some((page.items, next)))
+                }
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok::<T, GitLitError>)))
+        .try_flatten()
+    }
+}
+
+/// Parse a single relation (e.g. `next`) out of an RFC 5988 `Link` header.
+fn parse_link_header(header: &str, rel: &str) -> Option<String> {
+    for part in header.split(',') {
+        let mut segments = part.split(';');
+        let url = segments.next()?.trim();
+        let url = url.strip_prefix('<')?.strip_suffix('>')?;
+        for attr in segments {
+            let attr = attr.trim();
+            if let Some(value) = attr.strip_prefix("rel=") {
+                let value = value.trim_matches('"');
+                if value == rel {
+                    return Some(url.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// How the client authenticates against the server.
+///
+/// `Anonymous` sends no auth header (fine for public `list_repos`/`branches`),
+/// `Token` attaches a personal access token directly and bypasses the `/login`
+/// round-trip and the on-disk [`TokenStore`], and `UserPass` uses the classic
+/// username/password exchange whose bearer token is persisted by [`login`].
+///
+/// [`login`]: GitLitClient::login
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    Anonymous,
+    Token(String),
+    UserPass { login: String, password: String },
+}
+
+impl Default for Credentials {
+    fn default() -> Self {
+        Credentials::UserPass { login: String::new(), password: String::new() }
+    }
+}
+
+/// Which HTTP auth header a backend attaches to its requests. GitHub, Forgejo
+/// and GitLit use `Authorization: Bearer`; GitLab uses `PRIVATE-TOKEN`. A
+/// [`Page`] remembers the scheme of the request that produced it so follow-up
+/// pages re-authenticate the same way.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AuthScheme {
+    Bearer,
+    PrivateToken,
+}
+
+/// Decodes a paginated response body into `Vec<T>`. The default
+/// [`decode_json`] deserializes the common structs directly; forge backends
+/// supply a custom decoder that maps each forge's own JSON shape into them.
+/// It is stored on the [`Page`] so follow-up pages decode identically.
+pub(crate) type PageDecoder<T> = fn(&[u8]) -> Result<Vec<T>, GitLitError>;
+
+/// The default [`PageDecoder`]: deserialize the body straight into `Vec<T>`.
+pub(crate) fn decode_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<Vec<T>, GitLitError> {
+    Ok(serde_json::from_slice::<Vec<T>>(bytes)?)
+}
+
+/// Builder for [`GitLitClient`]. Construct with [`GitLitClient::builder`].
+pub struct GitLitClientBuilder {
+    url: String,
+    credentials: Credentials,
+    ca_cert: Option<PathBuf>,
+    accept_invalid_certs: bool,
+    ignore_version: bool,
+    allow_insecure_token_store: bool,
+}
+
+impl GitLitClientBuilder {
+    /// Set the credentials the client authenticates with (default
+    /// [`Credentials::UserPass`] backed by the [`TokenStore`]; `build` turns it
+    /// into [`Credentials::Anonymous`] when no token is stored).
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Trust an additional root CA from a PEM file, for self-hosted instances
+    /// fronted by a private/internal certificate authority.
+    pub fn ca_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_cert = Some(path.into());
+        self
+    }
+
+    /// Disable certificate validation. Intended for local development only.
+    pub fn accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Skip the `X-GITLIT-VERSION` response check, accepting any server version.
+    pub fn ignore_version(mut self, ignore: bool) -> Self {
+        self.ignore_version = ignore;
+        self
+    }
+
+    /// Load a token file even when it is group/world-accessible or owned by
+    /// another user.
+    pub fn allow_insecure_token_store(mut self, allow: bool) -> Self {
+        self.allow_insecure_token_store = allow;
+        self
+    }
+
+    pub fn build(self) -> Result<GitLitClient, GitLitError> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            VERSION_HEADER,
+            reqwest::header::HeaderValue::from_static(API_VERSION),
+        );
+        let mut builder = reqwest::Client::builder()
+            .user_agent("gitlit")
+            .default_headers(headers);
+        if let Some(path) = &self.ca_cert {
+            let pem = std::fs::read(path)?;
+            let cert = reqwest::Certificate::from_pem(&pem)?;
+            builder = builder.add_root_certificate(cert);
+        }
+        if self.accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        let http = builder.build()?;
+        let url = self.url.trim_end_matches('/').to_string();
+        let token_store = TokenStore::new(self.allow_insecure_token_store)?;
+        // A bare invocation (no `--token`, no `login`) leaves the default
+        // empty `UserPass`. Use a stored token if one exists, otherwise fall
+        // back to `Anonymous` so public reads keep working without a login
+        // instead of failing with "notoken".
+        let credentials = match self.credentials {
+            Credentials::UserPass { login, password } if login.is_empty() => {
+                if token_store.load(&url)?.is_some() {
+                    Credentials::UserPass { login, password }
+                } else {
+                    Credentials::Anonymous
+                }
+            }
+            other => other,
+        };
+        Ok(GitLitClient {
+            url,
+            http,
+            token_store,
+            credentials,
+            ignore_version: self.ignore_version,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct GitLitClient {
     url: String,
     http: reqwest::Client,
     token_store: TokenStore,
+    credentials: Credentials,
+    ignore_version: bool,
 }
 
 impl GitLitClient {
     pub fn new(url: impl Into<String>) -> Result<Self, GitLitError> {
-        let http = reqwest::Client::builder()
-            .user_agent("gitlit")
-            .build()?;
-        Ok(Self {
-            url: url.into().trim_end_matches('/').to_string(),
-            http,
-            token_store: TokenStore::new()?,
-        })
+        Self::builder(url).build()
+    }
+
+    /// Start building a client for `url`.
+    pub fn builder(url: impl Into<String>) -> GitLitClientBuilder {
+        GitLitClientBuilder {
+            url: url.into(),
+            credentials: Credentials::default(),
+            ca_cert: None,
+            accept_invalid_certs: false,
+            ignore_version: false,
+            allow_insecure_token_store: false,
+        }
     }
 
     fn host_key(&self) -> String {
@@ -86,9 +336,26 @@ impl GitLitClient {
     }
 
     fn auth(&self, req: reqwest::RequestBuilder) -> std::pin::Pin<Box<dyn std::future::Future<Output=Result<reqwest::RequestBuilder, GitLitError>> + Send + '_>> {
+        self.auth_with(req, AuthScheme::Bearer)
+    }
+
+    /// Attach the credentials to `req` using `scheme`. `Token` becomes an
+    /// `Authorization: Bearer`/`PRIVATE-TOKEN` header depending on the backend,
+    /// `UserPass` resolves the stored bearer token, and `Anonymous` is left
+    /// untouched so public reads work without any header.
+    pub(crate) fn auth_with(&self, req: reqwest::RequestBuilder, scheme: AuthScheme) -> std::pin::Pin<Box<dyn std::future::Future<Output=Result<reqwest::RequestBuilder, GitLitError>> + Send + '_>> {
         Box::pin(async move {
-            let token = self.get_token().await?;
-            Ok(req.bearer_auth(token))
+            match &self.credentials {
+                Credentials::Anonymous => Ok(req),
+                Credentials::Token(token) => Ok(match scheme {
+                    AuthScheme::Bearer => req.bearer_auth(token),
+                    AuthScheme::PrivateToken => req.header("PRIVATE-TOKEN", token),
+                }),
+                Credentials::UserPass { .. } => {
+                    let token = self.get_token().await?;
+                    Ok(req.bearer_auth(token))
+                }
+            }
         })
     }
 
@@ -106,6 +373,7 @@ impl GitLitClient {
             }))
             .send()
             .await?;
+        self.verify_version(&res)?;
         if !res.status().is_success() {
             return Err(GitLitError::Auth(format!("login failed: {}", res.status())));
         }
@@ -127,6 +395,7 @@ impl GitLitClient {
             }))
             .send()
             .await?;
+        self.verify_version(&res)?;
         if res.status() != reqwest::StatusCode::CREATED {
             return Err(GitLitError::Auth(format!("register failed: {}", res.status())));
         }
@@ -143,17 +412,83 @@ impl GitLitClient {
         }
         Err(GitLitError::Auth("notoken".to_string()))
     }
-    pub async fn list_repos(&self, owner: Option<&str>, filter: Option<&str>, q: Option<&str>) -> Result<Vec<Repository>, GitLitError> {
+    /// The shared HTTP client, exposed so forge backends can build their own
+    /// requests against each forge's routes.
+    pub(crate) fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    pub(crate) fn base(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) fn credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
+    /// Compare the server's `X-GITLIT-VERSION` header against [`API_VERSION`],
+    /// returning [`GitLitError::VersionMismatch`] on disagreement unless the
+    /// check was disabled via [`GitLitClientBuilder::ignore_version`].
+    pub(crate) fn verify_version(&self, res: &reqwest::Response) -> Result<(), GitLitError> {
+        if self.ignore_version {
+            return Ok(());
+        }
+        if let Some(server) = res.headers().get(VERSION_HEADER).and_then(|v| v.to_str().ok()) {
+            if server != API_VERSION {
+                return Err(GitLitError::VersionMismatch {
+                    client: API_VERSION.to_string(),
+                    server: server.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Send `req` and deserialize the body straight into a [`Page<T>`] with the
+    /// default [`decode_json`] decoder. `scheme` records how `req` was
+    /// authenticated so follow-up pages re-authenticate the same way.
+    pub(crate) async fn fetch_page<T: DeserializeOwned + Send + 'static>(&self, req: reqwest::RequestBuilder, scheme: AuthScheme) -> Result<Page<T>, GitLitError> {
+        self.fetch_page_with(req, scheme, decode_json::<T>).await
+    }
+
+    /// Send `req`, parse the RFC 5988 `Link` header, and run `decode` over the
+    /// body to build a [`Page<T>`] that can walk to neighbouring pages. Forge
+    /// backends pass a decoder that maps their own JSON into the common structs;
+    /// the decoder is carried on the page so each follow-up page maps the same.
+    pub(crate) async fn fetch_page_with<T: Send + 'static>(&self, req: reqwest::RequestBuilder, scheme: AuthScheme, decode: PageDecoder<T>) -> Result<Page<T>, GitLitError> {
+        let res = req.send().await?;
+        self.verify_version(&res)?;
+        if !res.status().is_success() {
+            return Err(GitLitError::Auth(format!("list failed: {}", res.status())));
+        }
+        let link = res
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let (next, prev, last) = match link {
+            Some(ref header) => (
+                parse_link_header(header, "next"),
+                parse_link_header(header, "prev"),
+                parse_link_header(header, "last"),
+            ),
+            None => (None, None, None),
+        };
+        let bytes = res.bytes().await?;
+        let items = decode(&bytes)?;
+        Ok(Page { items, next, prev, last, scheme, decode, client: self.clone() })
+    }
+
+    pub async fn list_repos(&self, owner: Option<&str>, filter: Option<&str>, q: Option<&str>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<Repository>, GitLitError> {
         let url = format!("{}/api/v1/repos", self.url);
         let mut req = self.http.get(url);
         if let Some(owner) = owner { req = req.query(&[("owner", owner)]); }
         if let Some(filter) = filter { req = req.query(&[("filter", filter)]); }
         if let Some(q) = q { req = req.query(&[("q", q)]); }
-        let res = req.send().await?;
-        if !res.status().is_success() {
-            return Err(GitLitError::Auth(format!("list_repos failed: {}", res.status())));
-        }
-        Ok(res.json::<Vec<Repository>>().await?)
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        if let Some(per_page) = per_page { req = req.query(&[("per_page", per_page)]); }
+        let req = self.auth(req).await?;
+        self.fetch_page(req, AuthScheme::Bearer).await
     }
 
 
@@ -166,6 +501,7 @@ impl GitLitClient {
         });
         let req = self.auth(req).await?;
         let res = req.send().await?;
+        self.verify_version(&res)?;
         if res.status() != reqwest::StatusCode::CREATED { return Err(GitLitError::Auth(format!("create_repo failed: {}", res.status())));}        
         Ok(res.json::<Repository>().await?)
     }
@@ -176,6 +512,7 @@ impl GitLitClient {
         let req = self.http.delete(url).query(&[("id", id)]);
         let req = self.auth(req).await?;
         let res = req.send().await?;
+        self.verify_version(&res)?;
         if !res.status().is_success() { return Err(GitLitError::Auth(format!("delete_repo failed: {}", res.status())));}        
         Ok(res.json::<OkResponse>().await?)
     }
@@ -183,7 +520,10 @@ impl GitLitClient {
 
     pub async fn branches(&self, id: &str) -> Result<BranchesResponse, GitLitError> {
         let url = format!("{}/api/v1/branches", self.url);
-        let res = self.http.get(url).query(&[("id", id)]).send().await?;
+        let req = self.http.get(url).query(&[("id", id)]);
+        let req = self.auth(req).await?;
+        let res = req.send().await?;
+        self.verify_version(&res)?;
         if !res.status().is_success() { return Err(GitLitError::Auth(format!("branches failed: {}", res.status())));}        
         Ok(res.json::<BranchesResponse>().await?)
     }
@@ -195,20 +535,22 @@ impl GitLitClient {
             .query(&[("id", id), ("branch", branch)]);
         let req = self.auth(req).await?;
         let res = req.send().await?;
+        self.verify_version(&res)?;
         if !res.status().is_success() {
             return Err(GitLitError::Auth(format!("delete_branch failed: {}", res.status())));
         }
         Ok(res.json::<BrancheDeleteResponse>().await?)
     }
 
-    pub async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>) -> Result<Vec<CommitInfo>, GitLitError> {
+    pub async fn commits(&self, id: &str, branch: Option<&str>, limit: Option<u32>, page: Option<u32>, per_page: Option<u32>) -> Result<Page<CommitInfo>, GitLitError> {
         let url = format!("{}/api/v1/commits", self.url);
         let mut req = self.http.get(url).query(&[("id", id)]);
         if let Some(b) = branch { req = req.query(&[("branch", b)]); }
         if let Some(l) = limit { req = req.query(&[("limit", l)]); }
-        let res = req.send().await?;
-        if !res.status().is_success() { return Err(GitLitError::Auth(format!("commits failed: {}", res.status())));}        
-        Ok(res.json::<Vec<CommitInfo>>().await?)
+        if let Some(page) = page { req = req.query(&[("page", page)]); }
+        if let Some(per_page) = per_page { req = req.query(&[("per_page", per_page)]); }
+        let req = self.auth(req).await?;
+        self.fetch_page(req, AuthScheme::Bearer).await
     }
 
 
@@ -218,8 +560,10 @@ impl GitLitClient {
         if let Some(p) = path { req = req.query(&[("path", p)]); }
         if let Some(b) = branch { req = req.query(&[("branch", b)]); }
         if let Some(c) = commit { req = req.query(&[("commit", c)]); }
+        let req = self.auth(req).await?;
         let res = req.send().await?;
-        if !res.status().is_success() { return Err(GitLitError::Auth(format!("content failed: {}", res.status())));}        
+        self.verify_version(&res)?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("content failed: {}", res.status())));}
         Ok(res.json::<ContentResponse>().await?)
     }
 
@@ -229,11 +573,61 @@ impl GitLitClient {
         if let Some(p) = path { req = req.query(&[("path", p)]); }
         if let Some(b) = branch { req = req.query(&[("branch", b)]); }
         if let Some(c) = commit { req = req.query(&[("commit", c)]); }
+        let req = self.auth(req).await?;
         let res = req.send().await?;
-        if !res.status().is_success() { return Err(GitLitError::Auth(format!("download failed: {}", res.status())));}        
+        self.verify_version(&res)?;
+        if !res.status().is_success() { return Err(GitLitError::Auth(format!("download failed: {}", res.status())));}
         Ok(res.bytes().await?.to_vec())
     }
 
+    pub async fn put_content(&self, id: &str, branch: &str, path: &str, bytes: &[u8], message: &str, parent_commit: Option<&str>) -> Result<CommitInfo, GitLitError> {
+        let change = FileChange {
+            path: path.to_string(),
+            content_base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+        };
+        self.commit_changes(id, branch, &[change], message, parent_commit).await
+    }
+
+    pub async fn commit_changes(&self, id: &str, branch: &str, changes: &[FileChange], message: &str, parent_commit: Option<&str>) -> Result<CommitInfo, GitLitError> {
+        let url = format!("{}/api/v1/commit", self.url);
+        let req = self.http.post(url).json(&serde_json::json!({
+            "id": id,
+            "branch": branch,
+            "message": message,
+            "parent": parent_commit,
+            "changes": changes,
+        }));
+        let req = self.auth(req).await?;
+        let res = req.send().await?;
+        self.verify_version(&res)?;
+        if res.status() == reqwest::StatusCode::CONFLICT {
+            return Err(GitLitError::Conflict(format!("non-fast-forward on {}", branch)));
+        }
+        if res.status() != reqwest::StatusCode::CREATED && !res.status().is_success() {
+            return Err(GitLitError::Auth(format!("commit failed: {}", res.status())));
+        }
+        Ok(res.json::<CommitInfo>().await?)
+    }
+
+    pub async fn create_branch(&self, id: &str, name: &str, from: Option<&str>) -> Result<Branch, GitLitError> {
+        let url = format!("{}/api/v1/branch", self.url);
+        let req = self.http.post(url).json(&serde_json::json!({
+            "id": id,
+            "name": name,
+            "from": from,
+        }));
+        let req = self.auth(req).await?;
+        let res = req.send().await?;
+        self.verify_version(&res)?;
+        if res.status() == reqwest::StatusCode::CONFLICT {
+            return Err(GitLitError::Conflict(format!("branch {} already exists", name)));
+        }
+        if res.status() != reqwest::StatusCode::CREATED && !res.status().is_success() {
+            return Err(GitLitError::Auth(format!("create_branch failed: {}", res.status())));
+        }
+        Ok(res.json::<Branch>().await?)
+    }
+
     pub async fn logout(&self) -> Result<(), GitLitError> {
         let token = match self.get_token().await {
             Ok(t) => t,
@@ -253,6 +647,7 @@ impl GitLitClient {
         let res = req
             .send()
             .await?;
+        self.verify_version(&res)?;
         if res.status() == reqwest::StatusCode::UNAUTHORIZED {
             let _ = self.token_store.delete(&self.host_key());
             return Err(GitLitError::Unauthorized);
@@ -265,45 +660,149 @@ impl GitLitClient {
     }
 }
 
+/// Turn a host string into the filesystem-safe stem used both as the token
+/// file name and, under the `keyring` feature, as the keyring entry key.
+fn sanitize_host(host: &str) -> String {
+    host
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+}
+
+/// Where bearer tokens are persisted. Defaults to a plaintext file store under
+/// the config directory; with the `keyring` feature it lives in the OS secret
+/// service instead.
 #[derive(Clone)]
-struct TokenStore {
-    path: PathBuf,
+enum TokenStore {
+    File { path: PathBuf, allow_insecure: bool },
+    #[cfg(feature = "keyring")]
+    Keyring,
 }
 
 impl TokenStore {
-    fn new() -> Result<Self, GitLitError> {
-        let proj = directories::ProjectDirs::from("com", "gitlit", "gitlit-cli")
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "no config dir"))?;
-        let path = proj.config_dir().join("tokens");
-        Ok(Self { path })
+    fn new(allow_insecure: bool) -> Result<Self, GitLitError> {
+        #[cfg(feature = "keyring")]
+        {
+            let _ = allow_insecure;
+            Ok(TokenStore::Keyring)
+        }
+        #[cfg(not(feature = "keyring"))]
+        {
+            let proj = directories::ProjectDirs::from("com", "gitlit", "gitlit-cli")
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "no config dir"))?;
+            let path = proj.config_dir().join("tokens");
+            Ok(TokenStore::File { path, allow_insecure })
+        }
     }
 
-    fn token_path(&self, host: &str) -> PathBuf {
-        let sanitized = host
-            .chars()
-            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
-            .collect::<String>();
-        self.path.join(format!("{}.token", sanitized))
+    fn token_path(&self, base: &Path, host: &str) -> PathBuf {
+        base.join(format!("{}.token", sanitize_host(host)))
     }
 
     fn load(&self, host: &str) -> Result<Option<String>, GitLitError> {
-        let p = self.token_path(host);
-        if !p.exists() { return Ok(None); }
-        let data = std::fs::read_to_string(&p)?;
-        let token = data.trim().to_string();
-        if token.is_empty() { Ok(None) } else { Ok(Some(token)) }
+        match self {
+            TokenStore::File { path, allow_insecure } => {
+                let p = self.token_path(path, host);
+                if !p.exists() { return Ok(None); }
+                if !allow_insecure {
+                    Self::check_permissions(&p)?;
+                }
+                let data = std::fs::read_to_string(&p)?;
+                let token = data.trim().to_string();
+                if token.is_empty() { Ok(None) } else { Ok(Some(token)) }
+            }
+            #[cfg(feature = "keyring")]
+            TokenStore::Keyring => {
+                let entry = keyring::Entry::new("gitlit-cli", &sanitize_host(host))
+                    .map_err(|e| GitLitError::Auth(e.to_string()))?;
+                match entry.get_password() {
+                    Ok(token) if !token.is_empty() => Ok(Some(token)),
+                    Ok(_) => Ok(None),
+                    Err(keyring::Error::NoEntry) => Ok(None),
+                    Err(e) => Err(GitLitError::Auth(e.to_string())),
+                }
+            }
+        }
     }
 
     fn save(&self, host: &str, token: &String) -> Result<(), GitLitError> {
-        let p = self.token_path(host);
-        if let Some(parent) = p.parent() { std::fs::create_dir_all(parent)?; }
-        std::fs::write(&p, token.as_bytes())?;
-        Ok(())
+        match self {
+            TokenStore::File { path, .. } => {
+                let p = self.token_path(path, host);
+                if let Some(parent) = p.parent() { std::fs::create_dir_all(parent)?; }
+                Self::write_private(&p, token.as_bytes())?;
+                Ok(())
+            }
+            #[cfg(feature = "keyring")]
+            TokenStore::Keyring => {
+                let entry = keyring::Entry::new("gitlit-cli", &sanitize_host(host))
+                    .map_err(|e| GitLitError::Auth(e.to_string()))?;
+                entry.set_password(token).map_err(|e| GitLitError::Auth(e.to_string()))
+            }
+        }
     }
 
     fn delete(&self, host: &str) -> Result<(), GitLitError> {
-        let p = self.token_path(host);
-        if p.exists() { std::fs::remove_file(p)?; }
+        match self {
+            TokenStore::File { path, .. } => {
+                let p = self.token_path(path, host);
+                if p.exists() { std::fs::remove_file(p)?; }
+                Ok(())
+            }
+            #[cfg(feature = "keyring")]
+            TokenStore::Keyring => {
+                let entry = keyring::Entry::new("gitlit-cli", &sanitize_host(host))
+                    .map_err(|e| GitLitError::Auth(e.to_string()))?;
+                match entry.delete_password() {
+                    Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                    Err(e) => Err(GitLitError::Auth(e.to_string())),
+                }
+            }
+        }
+    }
+
+    /// Write `bytes` to `path`, forcing `0600` mode on Unix so the token is not
+    /// readable by other users on the system. The permissions are reset after
+    /// opening so an existing, more permissive file is tightened rather than
+    /// left with its old mode (`OpenOptions::mode` only applies on creation).
+    fn write_private(path: &Path, bytes: &[u8]) -> Result<(), GitLitError> {
+        #[cfg(unix)]
+        {
+            use std::io::Write;
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            let mut file = std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .mode(0o600)
+                .open(path)?;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+            file.write_all(bytes)?;
+        }
+        #[cfg(not(unix))]
+        {
+            std::fs::write(path, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Refuse a token file that is readable by anyone but its owner: either its
+    /// mode grants group/world bits or it is owned by a different user.
+    #[cfg(unix)]
+    fn check_permissions(path: &Path) -> Result<(), GitLitError> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let meta = std::fs::metadata(path)?;
+        if meta.permissions().mode() & 0o077 != 0 {
+            return Err(GitLitError::InsecureTokenStore(path.to_path_buf()));
+        }
+        if meta.uid() != unsafe { libc::getuid() } {
+            return Err(GitLitError::InsecureTokenStore(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn check_permissions(_path: &Path) -> Result<(), GitLitError> {
         Ok(())
     }
 }